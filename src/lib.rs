@@ -1,9 +1,280 @@
 #![crate_name = "testcontainers_ext"]
 
+// This crate's manifest is maintained out of this tree. The pinned `bollard`
+// version must enable its `exec`, `volume`, and `network` API modules (`exec`
+// is used by `ImageExecExt`; `volume` and `network` by `label_attached_resources`
+// and the `PruneTargets::{volumes,networks}` pruning), and `Health::failing_streak` /
+// `HealthConfig::interval` must have the shapes this file assumes. A plain
+// `tar` dependency is required for `ImageExecExt::with_copy_into`'s archive
+// building. Keep the manifest's dependency list in sync with these `use`s
+// whenever either changes.
+
 use bollard::container::ListContainersOptions;
 use std::future::Future;
 use testcontainers::{ContainerRequest, Image, ImageExt, TestcontainersError};
 
+/// Which kinds of leaked Docker resources a pruning call should target.
+///
+/// [`ImagePruneExistedLabelExt::with_prune_existed_label`] only ever touches
+/// containers; [`ImagePruneExistedLabelExt::with_prune_existed_label_full`] lets
+/// callers opt into also reaping the volumes and networks that were labeled
+/// alongside them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PruneTargets {
+    pub containers: bool,
+    pub volumes: bool,
+    pub networks: bool,
+}
+
+impl PruneTargets {
+    /// Matches the historical behavior of `with_prune_existed_label`.
+    pub const CONTAINERS_ONLY: Self = Self {
+        containers: true,
+        volumes: false,
+        networks: false,
+    };
+
+    /// Reap containers, volumes and networks carrying the scope labels.
+    pub const ALL: Self = Self {
+        containers: true,
+        volumes: true,
+        networks: true,
+    };
+}
+
+impl Default for PruneTargets {
+    fn default() -> Self {
+        Self::CONTAINERS_ONLY
+    }
+}
+
+/// Shells out to the `docker` CLI to prune containers when the daemon API
+/// socket isn't reachable (rootless, remote-context, or Docker-Desktop-over-SSH
+/// setups) but the CLI itself is still usable. Only compiled in behind the
+/// `cli-backend` feature; [`with_prune_existed_label`](ImagePruneExistedLabelExt::with_prune_existed_label)
+/// falls back to it automatically when any of its bollard calls fails.
+#[cfg(feature = "cli-backend")]
+mod cli_backend {
+    use std::process::Command;
+    use testcontainers::TestcontainersError;
+
+    fn cli_error(context: &str, output: &std::process::Output) -> TestcontainersError {
+        TestcontainersError::Other(Box::new(std::io::Error::other(format!(
+            "{context}: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ))))
+    }
+
+    pub(crate) fn list_running_container_ids(
+        label_filters: &[String],
+    ) -> Result<Vec<String>, TestcontainersError> {
+        let mut command = Command::new("docker");
+        command.args(["ps", "--format", "{{.ID}}"]);
+        for filter in label_filters {
+            command.arg("--filter").arg(format!("label={filter}"));
+        }
+
+        let output = command
+            .output()
+            .map_err(|err| TestcontainersError::Other(Box::new(err)))?;
+
+        if !output.status.success() {
+            return Err(cli_error("docker ps failed", &output));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
+    pub(crate) fn stop_containers(ids: &[String]) -> Result<(), TestcontainersError> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+
+        let output = Command::new("docker")
+            .arg("stop")
+            .args(ids)
+            .output()
+            .map_err(|err| TestcontainersError::Other(Box::new(err)))?;
+
+        if !output.status.success() {
+            return Err(cli_error("docker stop failed", &output));
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn prune_containers(label_filters: &[String]) -> Result<(), TestcontainersError> {
+        let mut command = Command::new("docker");
+        command.args(["container", "prune", "--force"]);
+        for filter in label_filters {
+            command.arg("--filter").arg(format!("label={filter}"));
+        }
+
+        let output = command
+            .output()
+            .map_err(|err| TestcontainersError::Other(Box::new(err)))?;
+
+        if !output.status.success() {
+            return Err(cli_error("docker container prune failed", &output));
+        }
+
+        Ok(())
+    }
+}
+
+fn scope_label_filters(
+    scope: &str,
+    container_label: &str,
+) -> std::collections::HashMap<String, Vec<String>> {
+    let mut filters = std::collections::HashMap::<String, Vec<String>>::new();
+
+    filters.insert(
+        String::from("label"),
+        vec![
+            format!("{scope}.testcontainers.prune=true"),
+            format!("{scope}.testcontainers.scope={scope}"),
+            format!("{scope}.testcontainers.container={container_label}"),
+        ],
+    );
+
+    filters
+}
+
+/// Runs the bollard side of [`ImagePruneExistedLabelExt::with_prune_existed_label`]:
+/// connecting to the daemon socket, optionally stopping running matches, and
+/// pruning. bollard's client construction is lazy and typically succeeds even
+/// against an unreachable socket, so callers must treat *any* failure here —
+/// not just connection failure — as a signal to fall back to the `docker` CLI.
+async fn prune_containers_via_bollard(
+    filters: &std::collections::HashMap<String, Vec<String>>,
+    force: bool,
+) -> Result<(), TestcontainersError> {
+    use bollard::container::PruneContainersOptions;
+    use testcontainers::core::client::docker_client_instance;
+
+    let client = docker_client_instance().await?;
+
+    if force {
+        let result = client
+            .list_containers(Some(ListContainersOptions {
+                all: false,
+                filters: filters.clone(),
+                ..Default::default()
+            }))
+            .await
+            .map_err(|err| TestcontainersError::Other(Box::new(err)))?;
+
+        let remove_containers = result
+            .iter()
+            .filter(|c| matches!(c.state.as_deref(), Some("running")))
+            .flat_map(|c| c.id.as_deref())
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+
+        futures::future::try_join_all(
+            remove_containers
+                .iter()
+                .map(|c| client.stop_container(c, None)),
+        )
+        .await
+        .map_err(|error| TestcontainersError::Other(Box::new(error)))?;
+
+        #[cfg(feature = "tracing")]
+        if !remove_containers.is_empty() {
+            tracing::warn!(name = "stop running containers", result = ?remove_containers);
+        }
+    }
+
+    let _result = client
+        .prune_containers(Some(PruneContainersOptions {
+            filters: filters.clone(),
+        }))
+        .await
+        .map_err(|err| TestcontainersError::Other(Box::new(err)))?;
+
+    #[cfg(feature = "tracing")]
+    if _result
+        .containers_deleted
+        .as_ref()
+        .is_some_and(|c| !c.is_empty())
+    {
+        tracing::warn!(name = "prune existed containers", result = ?_result);
+    }
+
+    Ok(())
+}
+
+/// Applies the `{scope}.testcontainers.*` labels to the named volumes and
+/// user-defined network already configured on `container_request`, so that a
+/// later `prune_volumes`/`prune_networks` call filtered on those labels can
+/// actually find them.
+///
+/// Docker's volume/network create calls are idempotent by name but, if the
+/// resource already exists, return it unchanged rather than updating its
+/// labels — so this only labels volumes/networks that are fresh as of this
+/// call. Bind mounts (host paths) have no Docker-side object to label and are
+/// skipped.
+async fn label_attached_resources<I: Image>(
+    client: &bollard::Docker,
+    container_request: &ContainerRequest<I>,
+    labels: &std::collections::HashMap<String, String>,
+    targets: PruneTargets,
+) -> Result<(), TestcontainersError> {
+    use bollard::network::CreateNetworkOptions;
+    use bollard::volume::CreateVolumeOptions;
+    use testcontainers::core::MountType;
+
+    // `CreateVolumeOptions`/`CreateNetworkOptions` are generic over their
+    // string type, and `name` below is a borrowed `&str`, so `labels` needs
+    // to be the same borrowed shape rather than the owned map we were given.
+    let labels: std::collections::HashMap<&str, &str> = labels
+        .iter()
+        .map(|(key, value)| (key.as_str(), value.as_str()))
+        .collect();
+
+    if targets.volumes {
+        // Collected up front, rather than iterated in place, for two reasons:
+        // `container_request.mounts()`'s borrowing iterator isn't `Send` and
+        // can't be held across the `.await` below, and only named volumes
+        // (not bind mounts, which also set `source`) should be labeled.
+        let volume_names: Vec<&str> = container_request
+            .mounts()
+            .filter(|mount| matches!(mount.mount_type(), MountType::Volume))
+            .filter_map(|mount| mount.source())
+            .collect();
+
+        for volume_name in volume_names {
+            client
+                .create_volume(CreateVolumeOptions {
+                    name: volume_name,
+                    labels: labels.clone(),
+                    ..Default::default()
+                })
+                .await
+                .map_err(|err| TestcontainersError::Other(Box::new(err)))?;
+        }
+    }
+
+    if targets.networks {
+        if let Some(network) = container_request.network() {
+            client
+                .create_network(CreateNetworkOptions {
+                    name: network.as_str(),
+                    labels: labels.clone(),
+                    ..Default::default()
+                })
+                .await
+                .map_err(|err| TestcontainersError::Other(Box::new(err)))?;
+        }
+    }
+
+    Ok(())
+}
+
 pub trait ImagePruneExistedLabelExt<I>: Sized + ImageExt<I> + Send
 where
     I: Image,
@@ -11,6 +282,12 @@ where
     /// Given a scope, a container label, a prune flag, and a force flag,
     /// this method will prune the container if the prune flag is true.
     ///
+    /// Pruning normally goes through bollard's connection to the Docker daemon
+    /// socket. When any of those bollard calls fails and the `cli-backend`
+    /// feature is enabled, this falls back to shelling out to the `docker` CLI instead,
+    /// which also works against rootless/remote-context/SSH-forwarded daemons
+    /// where the socket path isn't reachable.
+    ///
     /// Example:
     ///
     /// ```
@@ -41,17 +318,12 @@ where
     ) -> impl Future<Output = Result<ContainerRequest<I>, TestcontainersError>> + Send {
         use std::collections::HashMap;
 
-        use bollard::container::PruneContainersOptions;
-        use testcontainers::core::client::docker_client_instance;
-
         let testcontainers_project_key = format!("{scope}.testcontainers.scope");
         let testcontainers_container_key = format!("{scope}.testcontainers.container");
         let testcontainers_prune_key = format!("{scope}.testcontainers.prune");
 
         async move {
             if prune {
-                let client = docker_client_instance().await?;
-
                 let mut filters = HashMap::<String, Vec<String>>::new();
 
                 filters.insert(
@@ -63,51 +335,385 @@ where
                     ],
                 );
 
-                if force {
-                    let result = client
-                        .list_containers(Some(ListContainersOptions {
-                            all: false,
+                // bollard's client construction is lazy, so an unreachable
+                // daemon socket (rootless/remote-context/SSH-forwarded setups)
+                // typically doesn't surface until the actual API calls run.
+                // Attempt the real work first and only fall back once it fails.
+                match prune_containers_via_bollard(&filters, force).await {
+                    Ok(()) => {}
+                    #[cfg(feature = "cli-backend")]
+                    Err(_) => {
+                        let label_filters = filters.remove("label").unwrap_or_default();
+
+                        if force {
+                            let running = cli_backend::list_running_container_ids(&label_filters)?;
+                            cli_backend::stop_containers(&running)?;
+
+                            #[cfg(feature = "tracing")]
+                            if !running.is_empty() {
+                                tracing::warn!(name = "stop running containers", result = ?running);
+                            }
+                        }
+
+                        cli_backend::prune_containers(&label_filters)?;
+
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!(name = "prune existed containers via docker CLI", scope = scope);
+                    }
+                    #[cfg(not(feature = "cli-backend"))]
+                    Err(err) => return Err(err),
+                }
+            }
+
+            let result = self.with_labels([
+                (testcontainers_prune_key, "true"),
+                (testcontainers_project_key, scope),
+                (testcontainers_container_key, container_label),
+            ]);
+
+            Ok(result)
+        }
+    }
+
+    /// Like [`ImagePruneExistedLabelExt::with_prune_existed_label`], but also
+    /// reaps the volumes and networks tagged with the same `{scope}.testcontainers.*`
+    /// labels when `prune` is true. Opt-in via `targets` so existing callers of
+    /// `with_prune_existed_label` keep their container-only behavior.
+    ///
+    /// Unlike `with_prune_existed_label`, this method has no `cli-backend`
+    /// fallback: it talks to the bollard client directly throughout and
+    /// returns [`TestcontainersError`] as soon as any call on it fails, so it
+    /// won't work against rootless/remote-context/SSH-forwarded daemons whose
+    /// socket bollard can't reach. Prefer `with_prune_existed_label` for those
+    /// setups if volume/network labeling isn't needed.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use tokio::runtime::Runtime;
+    /// use testcontainers::{core::{IntoContainerPort, WaitFor}, runners::AsyncRunner, GenericImage, ImageExt};
+    /// use testcontainers_ext::{ImagePruneExistedLabelExt, PruneTargets};
+    /// use anyhow::Result;
+    ///
+    /// async fn test () -> Result<()> {
+    ///   let container = GenericImage::new("redis", "7.2.4")
+    ///         .with_exposed_port(6379.tcp())
+    ///         .with_wait_for(WaitFor::message_on_stdout("Ready to accept connections"))
+    ///         .with_prune_existed_label_full("my-project-scope", "redis", true, true, PruneTargets::ALL).await?
+    ///         .start()
+    ///         .await?;
+    ///    Ok(())
+    /// }
+    ///
+    /// Runtime::new().unwrap().block_on(test()).unwrap();
+    /// ```
+    ///
+    fn with_prune_existed_label_full(
+        self,
+        scope: &str,
+        container_label: &str,
+        prune: bool,
+        force: bool,
+        targets: PruneTargets,
+    ) -> impl Future<Output = Result<ContainerRequest<I>, TestcontainersError>> + Send
+    where
+        Self: Into<ContainerRequest<I>>,
+    {
+        use bollard::container::PruneContainersOptions;
+        use bollard::network::PruneNetworksOptions;
+        use bollard::volume::PruneVolumesOptions;
+        use testcontainers::core::client::docker_client_instance;
+
+        let testcontainers_project_key = format!("{scope}.testcontainers.scope");
+        let testcontainers_container_key = format!("{scope}.testcontainers.container");
+        let testcontainers_prune_key = format!("{scope}.testcontainers.prune");
+
+        async move {
+            // Resolve the request once so it can both be inspected (for
+            // already-attached volumes/networks) and, at the end, relabeled —
+            // `Self` is only a generic `ImageExt` builder, not itself a
+            // `ContainerRequest`.
+            let container_request: ContainerRequest<I> = self.into();
+
+            // Label any volumes/networks already attached to this request up
+            // front, regardless of `prune`, mirroring how the container labels
+            // below are always applied — so a later run's prune call (on this
+            // or any other request sharing the scope) can find them.
+            if targets.volumes || targets.networks {
+                let client = docker_client_instance().await?;
+
+                let labels = std::collections::HashMap::from([
+                    (testcontainers_prune_key.clone(), String::from("true")),
+                    (testcontainers_project_key.clone(), scope.to_string()),
+                    (
+                        testcontainers_container_key.clone(),
+                        container_label.to_string(),
+                    ),
+                ]);
+
+                label_attached_resources(&client, &container_request, &labels, targets).await?;
+            }
+
+            if prune {
+                let client = docker_client_instance().await?;
+
+                let filters = scope_label_filters(scope, container_label);
+
+                if targets.containers {
+                    if force {
+                        let result = client
+                            .list_containers(Some(ListContainersOptions {
+                                all: false,
+                                filters: filters.clone(),
+                                ..Default::default()
+                            }))
+                            .await
+                            .map_err(|err| TestcontainersError::Other(Box::new(err)))?;
+
+                        let remove_containers = result
+                            .iter()
+                            .filter(|c| matches!(c.state.as_deref(), Some("running")))
+                            .flat_map(|c| c.id.as_deref())
+                            .collect::<Vec<_>>();
+
+                        futures::future::try_join_all(
+                            remove_containers
+                                .iter()
+                                .map(|c| client.stop_container(c, None)),
+                        )
+                        .await
+                        .map_err(|error| TestcontainersError::Other(Box::new(error)))?;
+
+                        #[cfg(feature = "tracing")]
+                        if !remove_containers.is_empty() {
+                            tracing::warn!(name = "stop running containers", result = ?remove_containers);
+                        }
+                    }
+
+                    let _result = client
+                        .prune_containers(Some(PruneContainersOptions {
                             filters: filters.clone(),
-                            ..Default::default()
                         }))
                         .await
                         .map_err(|err| TestcontainersError::Other(Box::new(err)))?;
 
-                    let remove_containers = result
-                        .iter()
-                        .filter(|c| matches!(c.state.as_deref(), Some("running")))
-                        .flat_map(|c| c.id.as_deref())
-                        .collect::<Vec<_>>();
+                    #[cfg(feature = "tracing")]
+                    if _result
+                        .containers_deleted
+                        .as_ref()
+                        .is_some_and(|c| !c.is_empty())
+                    {
+                        tracing::warn!(name = "prune existed containers", result = ?_result);
+                    }
+                }
 
-                    futures::future::try_join_all(
-                        remove_containers
-                            .iter()
-                            .map(|c| client.stop_container(c, None)),
-                    )
-                    .await
-                    .map_err(|error| TestcontainersError::Other(Box::new(error)))?;
+                if targets.volumes {
+                    let _result = client
+                        .prune_volumes(Some(PruneVolumesOptions {
+                            filters: filters.clone(),
+                        }))
+                        .await
+                        .map_err(|err| TestcontainersError::Other(Box::new(err)))?;
 
                     #[cfg(feature = "tracing")]
-                    if !remove_containers.is_empty() {
-                        tracing::warn!(name = "stop running containers", result = ?remove_containers);
+                    if _result
+                        .volumes_deleted
+                        .as_ref()
+                        .is_some_and(|v| !v.is_empty())
+                    {
+                        tracing::warn!(name = "prune existed volumes", result = ?_result);
                     }
                 }
 
-                let _result = client
-                    .prune_containers(Some(PruneContainersOptions { filters }))
+                if targets.networks {
+                    let _result = client
+                        .prune_networks(Some(PruneNetworksOptions {
+                            filters: filters.clone(),
+                        }))
+                        .await
+                        .map_err(|err| TestcontainersError::Other(Box::new(err)))?;
+
+                    #[cfg(feature = "tracing")]
+                    if _result
+                        .networks_deleted
+                        .as_ref()
+                        .is_some_and(|n| !n.is_empty())
+                    {
+                        tracing::warn!(name = "prune existed networks", result = ?_result);
+                    }
+                }
+            }
+
+            let result = container_request.with_labels([
+                (testcontainers_prune_key, "true"),
+                (testcontainers_project_key, scope),
+                (testcontainers_container_key, container_label),
+            ]);
+
+            Ok(result)
+        }
+    }
+
+    /// Given a scope, a container label, and an unhealthy grace period, inspect
+    /// already-running containers carrying the scope labels and stop/prune only
+    /// the ones Docker reports as `unhealthy` that have *stayed* unhealthy past
+    /// `unhealthy_timeout`. Containers that are healthy, starting, or only
+    /// briefly unhealthy are left alone so a slow-but-recovering container from
+    /// a prior run can be reused.
+    ///
+    /// Like [`ImagePruneExistedLabelExt::with_prune_existed_label_full`], this
+    /// method has no `cli-backend` fallback and depends on bollard reaching the
+    /// Docker daemon socket directly, so it won't work against
+    /// rootless/remote-context/SSH-forwarded daemons.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tokio::runtime::Runtime;
+    /// use testcontainers::{core::{IntoContainerPort, WaitFor}, runners::AsyncRunner, GenericImage, ImageExt};
+    /// use testcontainers_ext::ImagePruneExistedLabelExt;
+    /// use anyhow::Result;
+    ///
+    /// async fn test () -> Result<()> {
+    ///   let container = GenericImage::new("redis", "7.2.4")
+    ///         .with_exposed_port(6379.tcp())
+    ///         .with_wait_for(WaitFor::message_on_stdout("Ready to accept connections"))
+    ///         .with_reap_unhealthy_label("my-project-scope", "redis", Duration::from_secs(60)).await?
+    ///         .start()
+    ///         .await?;
+    ///    Ok(())
+    /// }
+    ///
+    /// Runtime::new().unwrap().block_on(test()).unwrap();
+    /// ```
+    ///
+    fn with_reap_unhealthy_label(
+        self,
+        scope: &str,
+        container_label: &str,
+        unhealthy_timeout: std::time::Duration,
+    ) -> impl Future<Output = Result<ContainerRequest<I>, TestcontainersError>> + Send {
+        use bollard::container::RemoveContainerOptions;
+        use testcontainers::core::client::docker_client_instance;
+
+        // Docker's default healthcheck interval, used when a container's
+        // `Config.Healthcheck.Interval` is unset (i.e. it inherits the image's
+        // baked-in `HEALTHCHECK` with no explicit interval).
+        const DEFAULT_HEALTHCHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+        let testcontainers_project_key = format!("{scope}.testcontainers.scope");
+        let testcontainers_container_key = format!("{scope}.testcontainers.container");
+        let testcontainers_prune_key = format!("{scope}.testcontainers.prune");
+
+        async move {
+            let client = docker_client_instance().await?;
+
+            let mut filters = scope_label_filters(scope, container_label);
+            filters.insert(String::from("health"), vec![String::from("unhealthy")]);
+
+            let candidates = client
+                .list_containers(Some(ListContainersOptions {
+                    all: false,
+                    filters,
+                    ..Default::default()
+                }))
+                .await
+                .map_err(|err| TestcontainersError::Other(Box::new(err)))?;
+
+            let mut wedged_containers = Vec::new();
+
+            for candidate in &candidates {
+                let Some(id) = candidate.id.as_deref() else {
+                    continue;
+                };
+
+                let inspect = client
+                    .inspect_container(id, None)
                     .await
                     .map_err(|err| TestcontainersError::Other(Box::new(err)))?;
 
-                #[cfg(feature = "tracing")]
-                if _result
-                    .containers_deleted
+                // Containers are stopped and removed below, so re-check the
+                // inspected labels exactly against all three
+                // `{scope}.testcontainers.*` values before treating this
+                // candidate as ours, rather than relying on the list filter
+                // alone.
+                let candidate_labels = inspect
+                    .config
+                    .as_ref()
+                    .and_then(|config| config.labels.as_ref());
+                let is_exact_match = candidate_labels.is_some_and(|labels| {
+                    labels.get(&testcontainers_prune_key).map(String::as_str) == Some("true")
+                        && labels.get(&testcontainers_project_key).map(String::as_str) == Some(scope)
+                        && labels.get(&testcontainers_container_key).map(String::as_str)
+                            == Some(container_label)
+                });
+
+                if !is_exact_match {
+                    continue;
+                }
+
+                // `Health.Log` only retains the 5 most recent checks, so its
+                // oldest entry keeps sliding forward for a container that has
+                // been wedged far longer than that. `FailingStreak` has no such
+                // cap: it counts every consecutive failure since the last pass,
+                // so `failing_streak * interval` is a reliable lower bound on
+                // how long the container has been unhealthy.
+                let failing_streak = inspect
+                    .state
+                    .as_ref()
+                    .and_then(|state| state.health.as_ref())
+                    .and_then(|health| health.failing_streak)
+                    .unwrap_or(0);
+
+                if failing_streak <= 0 {
+                    continue;
+                }
+
+                let interval = inspect
+                    .config
                     .as_ref()
-                    .is_some_and(|c| !c.is_empty())
-                {
-                    tracing::warn!(name = "prune existed containers", result = ?_result);
+                    .and_then(|config| config.healthcheck.as_ref())
+                    .and_then(|healthcheck| healthcheck.interval)
+                    .filter(|interval| *interval > 0)
+                    .map(|interval| std::time::Duration::from_nanos(interval as u64))
+                    .unwrap_or(DEFAULT_HEALTHCHECK_INTERVAL);
+
+                let unhealthy_for = interval.saturating_mul(failing_streak as u32);
+
+                if unhealthy_for > unhealthy_timeout {
+                    wedged_containers.push(id.to_string());
                 }
             }
 
+            if !wedged_containers.is_empty() {
+                futures::future::try_join_all(
+                    wedged_containers
+                        .iter()
+                        .map(|id| client.stop_container(id, None)),
+                )
+                .await
+                .map_err(|error| TestcontainersError::Other(Box::new(error)))?;
+
+                #[cfg(feature = "tracing")]
+                tracing::warn!(name = "stop wedged unhealthy containers", result = ?wedged_containers);
+
+                futures::future::try_join_all(wedged_containers.iter().map(|id| {
+                    client.remove_container(
+                        id,
+                        Some(RemoveContainerOptions {
+                            force: true,
+                            ..Default::default()
+                        }),
+                    )
+                }))
+                .await
+                .map_err(|error| TestcontainersError::Other(Box::new(error)))?;
+
+                #[cfg(feature = "tracing")]
+                tracing::warn!(name = "removed wedged unhealthy containers", result = ?wedged_containers);
+            }
+
             let result = self.with_labels([
                 (testcontainers_prune_key, "true"),
                 (testcontainers_project_key, scope),
@@ -170,3 +776,410 @@ where
     I: Image,
 {
 }
+
+/// Error returned by [`ImageStartupGuardExt`] when a container fails to come
+/// up, or come up healthy, within its configured deadline.
+#[derive(Debug)]
+pub enum StartupGuardError {
+    /// `start()` did not return within the configured startup timeout.
+    StartupTimeout(std::time::Duration),
+    /// The container never reported `healthy` via `docker inspect` before the deadline.
+    HealthTimeout(std::time::Duration),
+    /// The underlying Docker call failed.
+    Testcontainers(TestcontainersError),
+}
+
+impl std::fmt::Display for StartupGuardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StartupGuardError::StartupTimeout(timeout) => {
+                write!(f, "container did not start within {timeout:?}")
+            }
+            StartupGuardError::HealthTimeout(timeout) => {
+                write!(f, "container did not become healthy within {timeout:?}")
+            }
+            StartupGuardError::Testcontainers(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for StartupGuardError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StartupGuardError::Testcontainers(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<TestcontainersError> for StartupGuardError {
+    fn from(err: TestcontainersError) -> Self {
+        StartupGuardError::Testcontainers(err)
+    }
+}
+
+impl From<testcontainers::core::client::ClientError> for StartupGuardError {
+    fn from(err: testcontainers::core::client::ClientError) -> Self {
+        StartupGuardError::Testcontainers(TestcontainersError::from(err))
+    }
+}
+
+/// Bounds how long a container is given to start, and optionally to become
+/// `healthy`, before giving up.
+///
+/// This composes naturally with [`ImagePruneExistedLabelExt`], giving a
+/// single fluent chain: prune stale leftovers, start, then block until the
+/// replacement is genuinely healthy.
+pub trait ImageStartupGuardExt<I>: Sized + ImageExt<I> + Send + testcontainers::runners::AsyncRunner<I>
+where
+    I: Image + Sync,
+{
+    /// Starts the container, failing with [`StartupGuardError::StartupTimeout`]
+    /// if the image's own wait strategy hasn't succeeded within `timeout`.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tokio::runtime::Runtime;
+    /// use testcontainers::{core::{IntoContainerPort, WaitFor}, GenericImage, ImageExt};
+    /// use testcontainers_ext::ImageStartupGuardExt;
+    /// use anyhow::Result;
+    ///
+    /// async fn test () -> Result<()> {
+    ///   let container = GenericImage::new("redis", "7.2.4")
+    ///         .with_exposed_port(6379.tcp())
+    ///         .with_wait_for(WaitFor::message_on_stdout("Ready to accept connections"))
+    ///         .with_start_timeout(Duration::from_secs(30))
+    ///         .await?;
+    ///    Ok(())
+    /// }
+    ///
+    /// Runtime::new().unwrap().block_on(test()).unwrap();
+    /// ```
+    ///
+    fn with_start_timeout(
+        self,
+        timeout: std::time::Duration,
+    ) -> impl Future<Output = Result<testcontainers::ContainerAsync<I>, StartupGuardError>> + Send
+    {
+        async move {
+            match tokio::time::timeout(timeout, self.start()).await {
+                Ok(result) => result.map_err(StartupGuardError::from),
+                Err(_) => Err(StartupGuardError::StartupTimeout(timeout)),
+            }
+        }
+    }
+
+    /// Starts the container, then polls `docker inspect` every `poll_interval`
+    /// until `State.Health.Status` reports `healthy`, failing with
+    /// [`StartupGuardError::HealthTimeout`] if that hasn't happened within `timeout`.
+    ///
+    /// Intended for images with a `HEALTHCHECK`; for images without one this
+    /// will simply time out, since Docker never reports a health status.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tokio::runtime::Runtime;
+    /// use testcontainers::{core::{IntoContainerPort, WaitFor}, GenericImage, ImageExt};
+    /// use testcontainers_ext::{ImagePruneExistedLabelExt, ImageStartupGuardExt};
+    /// use anyhow::Result;
+    ///
+    /// async fn test () -> Result<()> {
+    ///   let container = GenericImage::new("redis", "7.2.4")
+    ///         .with_exposed_port(6379.tcp())
+    ///         .with_wait_for(WaitFor::message_on_stdout("Ready to accept connections"))
+    ///         .with_prune_existed_label("my-project-scope", "redis", true, true)
+    ///         .await?
+    ///         .with_wait_for_healthy(Duration::from_secs(1), Duration::from_secs(30))
+    ///         .await?;
+    ///    Ok(())
+    /// }
+    ///
+    /// Runtime::new().unwrap().block_on(test()).unwrap();
+    /// ```
+    ///
+    fn with_wait_for_healthy(
+        self,
+        poll_interval: std::time::Duration,
+        timeout: std::time::Duration,
+    ) -> impl Future<Output = Result<testcontainers::ContainerAsync<I>, StartupGuardError>> + Send
+    {
+        use testcontainers::core::client::docker_client_instance;
+
+        async move {
+            let container = self.start().await.map_err(StartupGuardError::from)?;
+
+            let client = docker_client_instance().await?;
+            let id = container.id().to_string();
+
+            let mut waited = std::time::Duration::ZERO;
+
+            loop {
+                let inspect = client
+                    .inspect_container(&id, None)
+                    .await
+                    .map_err(|err| {
+                        StartupGuardError::Testcontainers(TestcontainersError::Other(Box::new(err)))
+                    })?;
+
+                let healthy = inspect
+                    .state
+                    .as_ref()
+                    .and_then(|state| state.health.as_ref())
+                    .and_then(|health| health.status)
+                    .is_some_and(|status| status == bollard::models::HealthStatusEnum::HEALTHY);
+
+                if healthy {
+                    return Ok(container);
+                }
+
+                if waited >= timeout {
+                    return Err(StartupGuardError::HealthTimeout(timeout));
+                }
+
+                tokio::time::sleep(poll_interval).await;
+                waited += poll_interval;
+            }
+        }
+    }
+}
+
+impl<R, I> ImageStartupGuardExt<I> for R
+where
+    R: Sized + ImageExt<I> + Send + testcontainers::runners::AsyncRunner<I>,
+    I: Image + Sync,
+{
+}
+
+/// Error returned by [`ImageExecExt`].
+#[derive(Debug)]
+pub enum ExecGuardError {
+    /// `with_startup_exec` ran to completion but exited with a code other than
+    /// the one the caller expected.
+    UnexpectedExitCode { expected: i64, actual: i64 },
+    /// Reading the local path to seed into the container, or building the tar
+    /// archive for it, failed.
+    Io(std::io::Error),
+    /// The underlying Docker call failed.
+    Testcontainers(TestcontainersError),
+}
+
+impl std::fmt::Display for ExecGuardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExecGuardError::UnexpectedExitCode { expected, actual } => write!(
+                f,
+                "startup exec exited with code {actual}, expected {expected}"
+            ),
+            ExecGuardError::Io(err) => write!(f, "{err}"),
+            ExecGuardError::Testcontainers(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for ExecGuardError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ExecGuardError::Io(err) => Some(err),
+            ExecGuardError::Testcontainers(err) => Some(err),
+            ExecGuardError::UnexpectedExitCode { .. } => None,
+        }
+    }
+}
+
+impl From<TestcontainersError> for ExecGuardError {
+    fn from(err: TestcontainersError) -> Self {
+        ExecGuardError::Testcontainers(err)
+    }
+}
+
+impl From<std::io::Error> for ExecGuardError {
+    fn from(err: std::io::Error) -> Self {
+        ExecGuardError::Io(err)
+    }
+}
+
+impl From<testcontainers::core::client::ClientError> for ExecGuardError {
+    fn from(err: testcontainers::core::client::ClientError) -> Self {
+        ExecGuardError::Testcontainers(TestcontainersError::from(err))
+    }
+}
+
+/// Readiness gating and fixture seeding via in-container exec, rounding out
+/// the crate beyond pruning and logging with the setup steps pure port/log
+/// waits can't express.
+pub trait ImageExecExt<I>: Sized + ImageExt<I> + Send + testcontainers::runners::AsyncRunner<I>
+where
+    I: Image + Sync,
+{
+    /// Starts the container, then runs `cmd` inside it as a readiness gate
+    /// (e.g. `["redis-cli", "ping"]`), failing with
+    /// [`ExecGuardError::UnexpectedExitCode`] if it doesn't exit with
+    /// `expected_exit_code`.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use tokio::runtime::Runtime;
+    /// use testcontainers::{core::{IntoContainerPort, WaitFor}, GenericImage, ImageExt};
+    /// use testcontainers_ext::ImageExecExt;
+    /// use anyhow::Result;
+    ///
+    /// async fn test () -> Result<()> {
+    ///   let container = GenericImage::new("redis", "7.2.4")
+    ///         .with_exposed_port(6379.tcp())
+    ///         .with_wait_for(WaitFor::message_on_stdout("Ready to accept connections"))
+    ///         .with_startup_exec(vec!["redis-cli".to_string(), "ping".to_string()], 0)
+    ///         .await?;
+    ///    Ok(())
+    /// }
+    ///
+    /// Runtime::new().unwrap().block_on(test()).unwrap();
+    /// ```
+    ///
+    fn with_startup_exec(
+        self,
+        cmd: Vec<String>,
+        expected_exit_code: i64,
+    ) -> impl Future<Output = Result<testcontainers::ContainerAsync<I>, ExecGuardError>> + Send {
+        use bollard::exec::{CreateExecOptions, StartExecResults};
+        use futures::StreamExt;
+        use testcontainers::core::client::docker_client_instance;
+
+        async move {
+            let container = self.start().await.map_err(ExecGuardError::from)?;
+
+            let client = docker_client_instance().await?;
+            let id = container.id().to_string();
+
+            let exec = client
+                .create_exec(
+                    &id,
+                    CreateExecOptions {
+                        cmd: Some(cmd),
+                        attach_stdout: Some(true),
+                        attach_stderr: Some(true),
+                        ..Default::default()
+                    },
+                )
+                .await
+                .map_err(|err| {
+                    ExecGuardError::Testcontainers(TestcontainersError::Other(Box::new(err)))
+                })?;
+
+            if let StartExecResults::Attached { mut output, .. } = client
+                .start_exec(&exec.id, None)
+                .await
+                .map_err(|err| {
+                    ExecGuardError::Testcontainers(TestcontainersError::Other(Box::new(err)))
+                })?
+            {
+                while output.next().await.is_some() {}
+            }
+
+            let inspect = client.inspect_exec(&exec.id).await.map_err(|err| {
+                ExecGuardError::Testcontainers(TestcontainersError::Other(Box::new(err)))
+            })?;
+
+            match inspect.exit_code {
+                Some(actual) if actual == expected_exit_code => Ok(container),
+                Some(actual) => Err(ExecGuardError::UnexpectedExitCode {
+                    expected: expected_exit_code,
+                    actual,
+                }),
+                None => Err(ExecGuardError::UnexpectedExitCode {
+                    expected: expected_exit_code,
+                    actual: -1,
+                }),
+            }
+        }
+    }
+
+    /// Starts the container, then tars up `local_path` and uploads it into
+    /// `container_path` via Docker's upload-archive API, seeding fixture files
+    /// before the workload starts using them.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use tokio::runtime::Runtime;
+    /// use testcontainers::{core::{IntoContainerPort, WaitFor}, GenericImage, ImageExt};
+    /// use testcontainers_ext::ImageExecExt;
+    /// use anyhow::Result;
+    ///
+    /// async fn test () -> Result<()> {
+    ///   let container = GenericImage::new("redis", "7.2.4")
+    ///         .with_exposed_port(6379.tcp())
+    ///         .with_wait_for(WaitFor::message_on_stdout("Ready to accept connections"))
+    ///         .with_copy_into("./fixtures/redis.conf", "/usr/local/etc/redis")
+    ///         .await?;
+    ///    Ok(())
+    /// }
+    ///
+    /// Runtime::new().unwrap().block_on(test()).unwrap();
+    /// ```
+    ///
+    fn with_copy_into(
+        self,
+        local_path: impl AsRef<std::path::Path> + Send,
+        container_path: impl Into<String> + Send,
+    ) -> impl Future<Output = Result<testcontainers::ContainerAsync<I>, ExecGuardError>> + Send {
+        use bollard::container::UploadToContainerOptions;
+        use testcontainers::core::client::docker_client_instance;
+
+        async move {
+            let container = self.start().await.map_err(ExecGuardError::from)?;
+
+            let client = docker_client_instance().await?;
+            let id = container.id().to_string();
+            let container_path = container_path.into();
+
+            let local_path = local_path.as_ref();
+            let archive_name = local_path
+                .file_name()
+                .ok_or_else(|| {
+                    std::io::Error::new(std::io::ErrorKind::InvalidInput, "local_path has no file name")
+                })?
+                .to_string_lossy()
+                .into_owned();
+
+            let mut archive = Vec::new();
+            {
+                let mut builder = tar::Builder::new(&mut archive);
+                if local_path.is_dir() {
+                    builder.append_dir_all(&archive_name, local_path)?;
+                } else {
+                    let mut file = std::fs::File::open(local_path)?;
+                    builder.append_file(&archive_name, &mut file)?;
+                }
+                builder.finish()?;
+            }
+
+            client
+                .upload_to_container(
+                    &id,
+                    Some(UploadToContainerOptions {
+                        path: container_path,
+                        ..Default::default()
+                    }),
+                    archive.into(),
+                )
+                .await
+                .map_err(|err| {
+                    ExecGuardError::Testcontainers(TestcontainersError::Other(Box::new(err)))
+                })?;
+
+            Ok(container)
+        }
+    }
+}
+
+impl<R, I> ImageExecExt<I> for R
+where
+    R: Sized + ImageExt<I> + Send + testcontainers::runners::AsyncRunner<I>,
+    I: Image + Sync,
+{
+}